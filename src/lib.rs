@@ -3,8 +3,9 @@ use rand::Rng;
 
 pub mod input {
     use evdev::{
-        uinput::VirtualDevice, uinput::VirtualDeviceBuilder, AttributeSet, BusType, EventType,
-        InputEvent, InputId, Key, RelativeAxisType,
+        uinput::UinputAbsSetup, uinput::VirtualDevice, uinput::VirtualDeviceBuilder, AbsInfo,
+        AbsoluteAxisType, AttributeSet, BusType, EventType, InputEvent, InputId, Key,
+        RelativeAxisType,
     };
     use std::{thread::sleep, time::Duration};
 
@@ -30,10 +31,39 @@ pub mod input {
                 }
             }
         }
+
+        pub enum KeyboardError {
+            BuilderInitError,
+            KeysAssignError,
+            CreateDeviceError,
+            KeyEventEmitError,
+        }
+
+        impl KeyboardError {
+            pub fn message(&self) -> &'static str {
+                match self {
+                    Self::BuilderInitError => "Cannot initialize device builder",
+                    Self::KeysAssignError => "Cannot assign keys to virtual keyboard",
+                    Self::CreateDeviceError => "Cannot create virtual keyboard",
+                    Self::KeyEventEmitError => "Cannot send key event from virtual keyboard",
+                }
+            }
+        }
+    }
+
+    /// Bounding box for absolute positioning mode, in device coordinate units.
+    pub struct AbsoluteBounds {
+        pub min_x: i32,
+        pub max_x: i32,
+        pub min_y: i32,
+        pub max_y: i32,
     }
 
     pub struct Mouse {
         device: VirtualDevice,
+        /// Last known pointer position, kept in sync on every relative or
+        /// absolute emit so callers can bound movement without querying the OS.
+        pub current: (i32, i32),
     }
 
     impl Mouse {
@@ -45,6 +75,8 @@ pub mod input {
             let mut axes: AttributeSet<RelativeAxisType> = AttributeSet::new();
             axes.insert(RelativeAxisType::REL_X);
             axes.insert(RelativeAxisType::REL_Y);
+            axes.insert(RelativeAxisType::REL_WHEEL);
+            axes.insert(RelativeAxisType::REL_HWHEEL);
 
             let mouse = VirtualDeviceBuilder::new()
                 .or(Err(errors::MouseError::BuilderInitError))?
@@ -58,13 +90,54 @@ pub mod input {
                 .or(Err(errors::MouseError::CreateDeviceError))?;
             sleep(Duration::from_millis(150));
 
-            Ok(Mouse { device: mouse })
+            Ok(Mouse {
+                device: mouse,
+                current: (0, 0),
+            })
+        }
+
+        pub fn new_absolute(bounds: AbsoluteBounds) -> Result<Self, errors::MouseError> {
+            let mut keys: AttributeSet<Key> = AttributeSet::new();
+            keys.insert(Key::BTN_LEFT);
+            keys.insert(Key::BTN_RIGHT);
+
+            let abs_x = UinputAbsSetup::new(
+                AbsoluteAxisType::ABS_X,
+                AbsInfo::new(bounds.min_x, bounds.min_x, bounds.max_x, 0, 0, 0),
+            );
+            let abs_y = UinputAbsSetup::new(
+                AbsoluteAxisType::ABS_Y,
+                AbsInfo::new(bounds.min_y, bounds.min_y, bounds.max_y, 0, 0, 0),
+            );
+
+            let mouse = VirtualDeviceBuilder::new()
+                .or(Err(errors::MouseError::BuilderInitError))?
+                .name("KPRS mouse device")
+                .with_keys(&keys)
+                .or(Err(errors::MouseError::KeysAssignError))?
+                .with_absolute_axis(&abs_x)
+                .or(Err(errors::MouseError::AxesAssignError))?
+                .with_absolute_axis(&abs_y)
+                .or(Err(errors::MouseError::AxesAssignError))?
+                .input_id(InputId::new(BusType::BUS_USB, 0x0001, 0x0001, 0x0001))
+                .build()
+                .or(Err(errors::MouseError::CreateDeviceError))?;
+            sleep(Duration::from_millis(150));
+
+            Ok(Mouse {
+                device: mouse,
+                current: (bounds.min_x, bounds.min_y),
+            })
         }
 
         fn move_event(axis: RelativeAxisType, value: i32) -> InputEvent {
             InputEvent::new(EventType::RELATIVE, axis.0, value)
         }
 
+        fn absolute_event(axis: AbsoluteAxisType, value: i32) -> InputEvent {
+            InputEvent::new(EventType::ABSOLUTE, axis.0, value)
+        }
+
         fn key_event(key: Key, value: i32) -> InputEvent {
             InputEvent::new(EventType::KEY, key.code(), value)
         }
@@ -107,15 +180,246 @@ pub mod input {
                     Self::move_event(RelativeAxisType::REL_Y, y),
                 ])
                 .or(Err(errors::MouseError::AxisEventEmitError))?;
+            self.current = (self.current.0 + x, self.current.1 + y);
+            Ok(())
+        }
+
+        /// Moves the pointer to an absolute position. Only meaningful on a
+        /// device built with [`Mouse::new_absolute`].
+        pub fn pointer_move_to(&mut self, x: i32, y: i32) -> Result<(), errors::MouseError> {
+            self.device
+                .emit(&[
+                    Self::absolute_event(AbsoluteAxisType::ABS_X, x),
+                    Self::absolute_event(AbsoluteAxisType::ABS_Y, y),
+                ])
+                .or(Err(errors::MouseError::AxisEventEmitError))?;
+            self.current = (x, y);
+            Ok(())
+        }
+
+        /// Moves the pointer by `(dx, dy)` over `steps` relative emits spread
+        /// across `duration`, easing in and out via a smoothstep curve
+        /// instead of jumping straight to the target. Falls back to a single
+        /// [`Mouse::pointer_move`] when `steps == 0`.
+        pub fn pointer_move_smooth(
+            &mut self,
+            dx: i32,
+            dy: i32,
+            steps: u32,
+            duration: Duration,
+        ) -> Result<(), errors::MouseError> {
+            if steps == 0 {
+                return self.pointer_move(dx, dy);
+            }
+
+            let step_duration = duration / steps;
+            let mut emitted = (0i32, 0i32);
+
+            for step in 1..=steps {
+                let target = if step == steps {
+                    (dx, dy)
+                } else {
+                    let t = step as f64 / steps as f64;
+                    let s = 3.0 * t * t - 2.0 * t * t * t;
+                    ((s * dx as f64).round() as i32, (s * dy as f64).round() as i32)
+                };
+
+                self.pointer_move(target.0 - emitted.0, target.1 - emitted.1)?;
+                emitted = target;
+                sleep(step_duration);
+            }
+
+            Ok(())
+        }
+
+        pub fn scroll(&mut self, vertical: i32, horizontal: i32) -> Result<(), errors::MouseError> {
+            self.device
+                .emit(&[
+                    Self::move_event(RelativeAxisType::REL_WHEEL, vertical),
+                    Self::move_event(RelativeAxisType::REL_HWHEEL, horizontal),
+                ])
+                .or(Err(errors::MouseError::AxisEventEmitError))?;
+            Ok(())
+        }
+    }
+
+    /// A virtual keyboard emitting harmless, no-op keys (modifiers on their
+    /// own, unmapped function keys, toggles pressed in matching pairs) so the
+    /// activity it produces has no observable side effect beyond registering
+    /// as input.
+    pub struct Keyboard {
+        device: VirtualDevice,
+    }
+
+    impl Keyboard {
+        pub fn new() -> Result<Self, errors::KeyboardError> {
+            let mut keys: AttributeSet<Key> = AttributeSet::new();
+            keys.insert(Key::KEY_LEFTSHIFT);
+            keys.insert(Key::KEY_F15);
+            keys.insert(Key::KEY_SCROLLLOCK);
+
+            let device = VirtualDeviceBuilder::new()
+                .or(Err(errors::KeyboardError::BuilderInitError))?
+                .name("KPRS keyboard device")
+                .with_keys(&keys)
+                .or(Err(errors::KeyboardError::KeysAssignError))?
+                .input_id(InputId::new(BusType::BUS_USB, 0x0001, 0x0001, 0x0001))
+                .build()
+                .or(Err(errors::KeyboardError::CreateDeviceError))?;
+            sleep(Duration::from_millis(150));
+
+            Ok(Keyboard { device })
+        }
+
+        fn key_event(key: Key, value: i32) -> InputEvent {
+            InputEvent::new(EventType::KEY, key.code(), value)
+        }
+
+        pub fn key_down(&mut self, key: Key) -> Result<(), errors::KeyboardError> {
+            self.device
+                .emit(&[Self::key_event(key, 1)])
+                .or(Err(errors::KeyboardError::KeyEventEmitError))?;
+            Ok(())
+        }
+
+        pub fn key_up(&mut self, key: Key) -> Result<(), errors::KeyboardError> {
+            self.device
+                .emit(&[Self::key_event(key, 0)])
+                .or(Err(errors::KeyboardError::KeyEventEmitError))?;
+            Ok(())
+        }
+
+        pub fn tap(&mut self, key: Key) -> Result<(), errors::KeyboardError> {
+            self.key_down(key)?;
+            sleep(Duration::from_millis(50));
+            self.key_up(key)?;
             Ok(())
         }
     }
 }
 
+pub mod patterns {
+    use rand::Rng;
+    use std::f64::consts::PI;
+
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub enum PatternKind {
+        Random,
+        Circle,
+        Figure8,
+    }
+
+    /// Produces a coherent stream of relative `(dx, dy)` steps tracing a
+    /// named shape around the origin, so idle movement reads as deliberate
+    /// cursor activity rather than uncorrelated jumps.
+    pub struct Pattern {
+        kind: PatternKind,
+        radius: f64,
+        theta: f64,
+        theta_step: f64,
+    }
+
+    impl Pattern {
+        pub fn new(kind: PatternKind, radius: i32) -> Self {
+            Pattern {
+                kind,
+                radius: radius as f64,
+                theta: 0.0,
+                theta_step: PI / 16.0,
+            }
+        }
+
+        fn circle_point(&self, theta: f64) -> (f64, f64) {
+            (self.radius * theta.cos(), self.radius * theta.sin())
+        }
+
+        fn figure8_point(&self, theta: f64) -> (f64, f64) {
+            (
+                self.radius * theta.cos(),
+                self.radius * theta.sin() * theta.cos(),
+            )
+        }
+
+        fn step(&mut self, point_at: fn(&Self, f64) -> (f64, f64)) -> (i32, i32) {
+            let previous = point_at(self, self.theta);
+            self.theta += self.theta_step;
+            let next = point_at(self, self.theta);
+            (
+                (next.0 - previous.0).round() as i32,
+                (next.1 - previous.1).round() as i32,
+            )
+        }
+
+        /// Returns the next relative `(dx, dy)` step for this pattern.
+        pub fn next_delta(&mut self) -> (i32, i32) {
+            match self.kind {
+                PatternKind::Random => {
+                    let mut rng = rand::thread_rng();
+                    let range = self.radius as i32;
+                    (rng.gen_range(-range..range), rng.gen_range(-range..range))
+                }
+                PatternKind::Circle => self.step(Self::circle_point),
+                PatternKind::Figure8 => self.step(Self::figure8_point),
+            }
+        }
+    }
+}
+
+pub mod activity {
+    use std::time::{Duration, Instant};
+
+    /// Diffs consecutive `rs_idle` polls against our own injected events so
+    /// the idle loop can tell a real user coming back apart from the idle
+    /// counter resetting because of our own synthetic input.
+    pub struct ActivityState {
+        last_idle: u64,
+        last_synthetic: Option<(Instant, i32)>,
+        is_user_active: bool,
+    }
+
+    impl ActivityState {
+        pub fn new() -> Self {
+            ActivityState {
+                last_idle: 0,
+                last_synthetic: None,
+                is_user_active: false,
+            }
+        }
+
+        /// Records that we just emitted a synthetic event of the given
+        /// magnitude (e.g. `|dx| + |dy|` for a pointer move).
+        pub fn record_synthetic(&mut self, magnitude: i32) {
+            self.last_synthetic = Some((Instant::now(), magnitude));
+        }
+
+        /// Feeds a freshly polled idle value, derives `is_user_active` from
+        /// whether the idle counter dropped without a synthetic event of
+        /// ours in flight within `grace`, and returns the new flag.
+        pub fn observe_idle(&mut self, idle: u64, grace: Duration) -> bool {
+            let dropped = idle < self.last_idle;
+            let ours = self
+                .last_synthetic
+                .map(|(at, _)| at.elapsed() < grace)
+                .unwrap_or(false);
+
+            self.is_user_active = dropped && !ours;
+            self.last_idle = idle;
+            self.is_user_active
+        }
+
+        pub fn is_user_active(&self) -> bool {
+            self.is_user_active
+        }
+    }
+}
+
 pub mod cli {
     use clap::{builder::ValueParser, Arg, ArgAction, Command};
+    use std::time::Duration;
 
     mod validators {
+        use std::time::Duration;
+
         pub fn idle_time(value: &str) -> Result<u64, &'static str> {
             match value.parse::<u64>() {
                 Ok(number) => Ok(number),
@@ -134,11 +438,55 @@ pub mod cli {
                 Err(_) => Err("must be valid positive integer"),
             }
         }
+        pub fn movement_mode(value: &str) -> Result<MovementMode, &'static str> {
+            match value {
+                "relative" => Ok(MovementMode::Relative),
+                "absolute" => Ok(MovementMode::Absolute),
+                _ => Err("must be one of: relative, absolute"),
+            }
+        }
+        pub fn grace_period(value: &str) -> Result<Duration, &'static str> {
+            match value.parse::<u64>() {
+                Ok(seconds) => Ok(Duration::from_secs(seconds)),
+                Err(_) => Err("must be valid positive integer"),
+            }
+        }
+        pub fn quiet_hours(value: &str) -> Result<(u8, u8), &'static str> {
+            let (start, end) = value
+                .split_once('-')
+                .ok_or("must be in START-END form, e.g. 9-17")?;
+            let parse_hour = |hour: &str| -> Result<u8, &'static str> {
+                hour.parse::<u8>()
+                    .ok()
+                    .filter(|hour| *hour < 24)
+                    .ok_or("hours must be integers between 0 and 23")
+            };
+            Ok((parse_hour(start)?, parse_hour(end)?))
+        }
+        pub fn pattern(value: &str) -> Result<crate::patterns::PatternKind, &'static str> {
+            use crate::patterns::PatternKind;
+            match value {
+                "random" => Ok(PatternKind::Random),
+                "circle" => Ok(PatternKind::Circle),
+                "figure8" => Ok(PatternKind::Figure8),
+                _ => Err("must be one of: random, circle, figure8"),
+            }
+        }
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub enum MovementMode {
+        Relative,
+        Absolute,
     }
 
     pub struct Args {
         pub idle_time: u64,
         pub mouse_range: i32,
+        pub movement_mode: MovementMode,
+        pub grace_period: Duration,
+        pub quiet_hours: Option<(u8, u8)>,
+        pub pattern: crate::patterns::PatternKind,
     }
 
     pub fn parse_args() -> Args {
@@ -163,6 +511,37 @@ pub mod cli {
                     .default_value("50")
                     .value_parser(ValueParser::new(validators::mouse_range)),
             )
+            .arg(
+                Arg::new("movement_mode")
+                    .help("Pointer movement mode: relative or absolute")
+                    .long("movement-mode")
+                    .action(ArgAction::Set)
+                    .default_value("relative")
+                    .value_parser(ValueParser::new(validators::movement_mode)),
+            )
+            .arg(
+                Arg::new("grace_period")
+                    .help("Seconds to stay quiet after real user input is detected")
+                    .long("grace-period")
+                    .action(ArgAction::Set)
+                    .default_value("30")
+                    .value_parser(ValueParser::new(validators::grace_period)),
+            )
+            .arg(
+                Arg::new("quiet_hours")
+                    .help("Hour range to never act in, as START-END (e.g. 9-17)")
+                    .long("quiet-hours")
+                    .action(ArgAction::Set)
+                    .value_parser(ValueParser::new(validators::quiet_hours)),
+            )
+            .arg(
+                Arg::new("pattern")
+                    .help("Movement pattern: random, circle or figure8")
+                    .long("pattern")
+                    .action(ArgAction::Set)
+                    .default_value("random")
+                    .value_parser(ValueParser::new(validators::pattern)),
+            )
             .get_matches();
 
         let idle_time: u64 = *cli
@@ -173,9 +552,27 @@ pub mod cli {
             .get_one("mouse_range")
             .expect("Validated and have default value");
 
+        let movement_mode: MovementMode = *cli
+            .get_one("movement_mode")
+            .expect("Validated and have default value");
+
+        let grace_period: Duration = *cli
+            .get_one("grace_period")
+            .expect("Validated and have default value");
+
+        let quiet_hours: Option<(u8, u8)> = cli.get_one("quiet_hours").copied();
+
+        let pattern: crate::patterns::PatternKind = *cli
+            .get_one("pattern")
+            .expect("Validated and have default value");
+
         Args {
             idle_time,
-            mouse_range
+            mouse_range,
+            movement_mode,
+            grace_period,
+            pattern,
+            quiet_hours,
         }
     }
 }
@@ -189,27 +586,135 @@ fn error(error: input::errors::MouseError) -> () {
     println!("{}", error.message());
 }
 
+fn error_exit_keyboard(err: input::errors::KeyboardError) -> ! {
+    error_keyboard(err);
+    std::process::exit(1)
+}
+
+fn error_keyboard(error: input::errors::KeyboardError) -> () {
+    println!("{}", error.message());
+}
+
+const ABSOLUTE_SCREEN_WIDTH: i32 = 1920;
+const ABSOLUTE_SCREEN_HEIGHT: i32 = 1080;
+
+/// Current hour of day in UTC, used to evaluate `--quiet-hours`.
+fn current_hour() -> u8 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is after the Unix epoch")
+        .as_secs();
+    ((since_epoch / 3600) % 24) as u8
+}
+
+fn in_quiet_hours(quiet_hours: Option<(u8, u8)>) -> bool {
+    match quiet_hours {
+        Some((start, end)) if start <= end => (start..end).contains(&current_hour()),
+        Some((start, end)) => !(end..start).contains(&current_hour()),
+        None => false,
+    }
+}
+
 pub fn start() {
-    let cli::Args { idle_time, mouse_range } = cli::parse_args();
+    let cli::Args {
+        idle_time,
+        mouse_range,
+        movement_mode,
+        grace_period,
+        quiet_hours,
+        pattern,
+    } = cli::parse_args();
+
+    let mut pattern = patterns::Pattern::new(pattern, mouse_range);
 
     println!("Idle time set to: {} minutes", idle_time);
     println!("When idle mouse will be moved by {}px in both axes", mouse_range);
 
-    let mut mouse = match input::Mouse::new() {
+    let mut mouse = match movement_mode {
+        cli::MovementMode::Relative => input::Mouse::new(),
+        cli::MovementMode::Absolute => input::Mouse::new_absolute(input::AbsoluteBounds {
+            min_x: 0,
+            max_x: ABSOLUTE_SCREEN_WIDTH,
+            min_y: 0,
+            max_y: ABSOLUTE_SCREEN_HEIGHT,
+        }),
+    };
+    let mut mouse = match mouse {
         Ok(instance) => instance,
         Err(error) => error_exit(error),
     };
 
+    let mut keyboard = match input::Keyboard::new() {
+        Ok(instance) => instance,
+        Err(error) => error_exit_keyboard(error),
+    };
+
+    const NOOP_KEYS: [evdev::Key; 3] = [
+        evdev::Key::KEY_LEFTSHIFT,
+        evdev::Key::KEY_F15,
+        evdev::Key::KEY_SCROLLLOCK,
+    ];
+
+    let mut activity = activity::ActivityState::new();
+    let mut cooldown_until: Option<std::time::Instant> = None;
+
     loop {
         sleep(Duration::from_secs(1));
 
         let idle = rs_idle::get_idle_time();
         let mut rng = rand::thread_rng();
+
+        if activity.observe_idle(idle, grace_period) {
+            println!("Real user activity detected, backing off for {:?}", grace_period);
+            cooldown_until = Some(std::time::Instant::now() + grace_period);
+        }
+
+        if cooldown_until.is_some_and(|until| std::time::Instant::now() < until) {
+            continue;
+        }
+        cooldown_until = None;
+
+        if in_quiet_hours(quiet_hours) {
+            continue;
+        }
+
         if idle > idle_time * 60 * 1000 {
-            let x: i32 = rng.gen_range(-mouse_range..mouse_range);
-            let y: i32 = rng.gen_range(-mouse_range..mouse_range);
-            println!("Idle: {}ms, moving mouse by x: {}, y: {}", idle, x, y);
-            mouse.pointer_move(x, y).unwrap_or_else(error);
+            if rng.gen_bool(0.1) {
+                let key = NOOP_KEYS[rng.gen_range(0..NOOP_KEYS.len())];
+                println!("Idle: {}ms, tapping key {:?}", idle, key);
+                keyboard.tap(key).unwrap_or_else(error_keyboard);
+                if key == evdev::Key::KEY_SCROLLLOCK {
+                    // Second press restores the Scroll Lock state.
+                    keyboard.tap(key).unwrap_or_else(error_keyboard);
+                }
+                activity.record_synthetic(1);
+            } else if rng.gen_bool(0.2) {
+                let vertical: i32 = rng.gen_range(-3..3);
+                println!("Idle: {}ms, scrolling wheel by {}", idle, vertical);
+                mouse.scroll(vertical, 0).unwrap_or_else(error);
+                activity.record_synthetic(vertical.abs());
+            } else {
+                let (dx, dy) = pattern.next_delta();
+                match movement_mode {
+                    cli::MovementMode::Relative => {
+                        println!("Idle: {}ms, moving mouse by x: {}, y: {}", idle, dx, dy);
+                        mouse
+                            .pointer_move_smooth(dx, dy, 20, Duration::from_millis(300))
+                            .unwrap_or_else(error);
+                        activity.record_synthetic(dx.abs() + dy.abs());
+                    }
+                    cli::MovementMode::Absolute => {
+                        let (current_x, current_y) = mouse.current;
+                        let x = (current_x + dx).clamp(0, ABSOLUTE_SCREEN_WIDTH);
+                        let y = (current_y + dy).clamp(0, ABSOLUTE_SCREEN_HEIGHT);
+                        println!("Idle: {}ms, moving mouse to x: {}, y: {}", idle, x, y);
+                        mouse.pointer_move_to(x, y).unwrap_or_else(error);
+                        activity.record_synthetic((x - current_x).abs() + (y - current_y).abs());
+                    }
+                }
+            }
         }
     }
 }